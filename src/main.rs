@@ -1,7 +1,8 @@
 use core::panic;
 use std::fmt::{Display, Formatter, Result};
+use std::time::{Duration, Instant};
 
-use rand::Rng;
+use rand::{Rng, RngCore};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum Tile {
@@ -10,21 +11,147 @@ enum Tile {
     Air,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Optimizer {
+    Genetic,
+    Annealing,
+}
+
+// Splits a single u64 seed into the four words Xoshiro256StarStar needs,
+// per Vigna & Blackman's recommended seeding procedure.
+struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+}
+
+// Seedable xoshiro256** generator (Vigna & Blackman). Reproducible and
+// avoids the thread-local lookup every `rand::thread_rng()` call pays.
+struct Xoshiro256StarStar {
+    s: [u64; 4],
+}
+
+impl Xoshiro256StarStar {
+    fn seed_from_u64(seed: u64) -> Xoshiro256StarStar {
+        let mut seeder = SplitMix64 { state: seed };
+        Xoshiro256StarStar {
+            s: [
+                seeder.next(),
+                seeder.next(),
+                seeder.next(),
+                seeder.next(),
+            ],
+        }
+    }
+}
+
+impl RngCore for Xoshiro256StarStar {
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let result = self.s[1]
+            .wrapping_mul(5)
+            .rotate_left(7)
+            .wrapping_mul(9);
+
+        let t = self.s[1] << 17;
+
+        self.s[2] ^= self.s[0];
+        self.s[3] ^= self.s[1];
+        self.s[1] ^= self.s[2];
+        self.s[0] ^= self.s[3];
+        self.s[2] ^= t;
+        self.s[3] = self.s[3].rotate_left(45);
+
+        result
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        let mut filled = 0;
+        while filled < dest.len() {
+            let chunk = self.next_u64().to_le_bytes();
+            let n = (dest.len() - filled).min(8);
+            dest[filled..filled + n].copy_from_slice(&chunk[..n]);
+            filled += n;
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> std::result::Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+// Per-term multipliers for the symmetry scores, replacing what used to be
+// hardcoded `* 50.0` literals so callers can weigh some symmetries over
+// others (e.g. favour rotational symmetry over mirror symmetry).
+#[derive(Debug, Clone, Copy)]
+struct SymmetryWeights {
+    vertical: f64,
+    horizontal: f64,
+    rotational_90: f64,
+    rotational_180: f64,
+    rotational_270: f64,
+    diagonal: f64,
+}
+
+impl Default for SymmetryWeights {
+    fn default() -> SymmetryWeights {
+        SymmetryWeights {
+            vertical: 50.0,
+            horizontal: 50.0,
+            rotational_90: 50.0,
+            rotational_180: 50.0,
+            rotational_270: 50.0,
+            diagonal: 50.0,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 struct Farm {
-    tiles: Vec<Vec<Tile>>,
+    tiles: Vec<Tile>,
     size_x: usize,
     size_y: usize,
+    sugar_count: usize,
+    vertical_matches: usize,
+    horizontal_matches: usize,
+    cached_score: i64,
+    weights: SymmetryWeights,
 }
 
-impl Farm {
-    fn new_rect(size_x: usize, size_y: usize) -> Farm {
-        let mut rows: Vec<Vec<Tile>> = vec![];
+// The tile writes and raw sugar/symmetry-match count deltas that flipping a
+// single tile would cause. `changes` may contain more than one tile because
+// `kill_sugar` can make a neighbouring sugar tile non-viable when its last
+// water neighbour disappears.
+struct MoveDelta {
+    changes: Vec<((usize, usize), Tile)>,
+    sugar_delta: i64,
+    vertical_delta: i64,
+    horizontal_delta: i64,
+}
 
+impl Farm {
+    fn new_rect(size_x: usize, size_y: usize, weights: SymmetryWeights) -> Farm {
         let mut farm = Farm {
-            tiles: rows,
+            tiles: vec![],
             size_x,
             size_y,
+            sugar_count: 0,
+            vertical_matches: 0,
+            horizontal_matches: 0,
+            cached_score: 0,
+            weights,
         };
 
         farm.populate_with_air();
@@ -34,35 +161,35 @@ impl Farm {
         farm
     }
 
-    fn new_square(size: usize) -> Farm {
-        Self::new_rect(size, size)
+    fn new_square(size: usize, weights: SymmetryWeights) -> Farm {
+        Self::new_rect(size, size, weights)
     }
 
     fn breed(a: &Farm, b: &Farm) -> Farm {
-        let mut rng = rand::thread_rng();
-
-        let mut rows: Vec<Vec<Tile>> = vec![];
-        for x in 0..a.size_x {
-            let mut row: Vec<Tile> = vec![];
-            for y in 0..a.size_y {
+        let mut tiles: Vec<Tile> = vec![];
+        for y in 0..a.size_y {
+            for x in 0..a.size_x {
                 let a_tile = a.get_tile(x, y).unwrap();
                 let b_tile = b.get_tile(x, y).unwrap();
 
-                let mut new_tile = match a_tile {
+                let new_tile = match a_tile {
                     Tile::Air => b_tile,
                     a_tile => a_tile,
                 };
 
-                row.push(new_tile);
+                tiles.push(new_tile);
             }
-
-            rows.push(row);
         }
 
         let mut new_farm = Farm {
-            tiles: rows,
+            tiles,
             size_x: a.size_x,
             size_y: b.size_y,
+            sugar_count: 0,
+            vertical_matches: 0,
+            horizontal_matches: 0,
+            cached_score: 0,
+            weights: a.weights,
         };
 
         new_farm.kill_sugar();
@@ -70,55 +197,36 @@ impl Farm {
         new_farm
     }
 
-    fn mutate(&mut self, mutation_factor: f32) {
-        let mut rows: Vec<Vec<Tile>> = vec![];
-        let mut rng = rand::thread_rng();
+    fn mutate<R: Rng>(&mut self, mutation_factor: f32, rng: &mut R) {
+        let mut tiles: Vec<Tile> = Vec::with_capacity(self.tiles.len());
 
-        for x in 0..self.size_x {
-            let mut row: Vec<Tile> = vec![];
-            for y in 0..self.size_y {
+        for y in 0..self.size_y {
+            for x in 0..self.size_x {
                 let num: f32 = rng.gen();
                 let tile = if num < mutation_factor {
-                    let num: i32 = rng.gen_range(0..3);
-                    match num {
-                        0 => Tile::Sugar,
-                        1 => Tile::Water,
-                        2 => Tile::Air,
-                        _ => panic!("invalid tile index"),
-                    }
+                    random_tile(rng)
                 } else {
-                    self.tiles[x][y]
+                    self.get_tile(x, y).unwrap()
                 };
-                row.push(tile);
+                tiles.push(tile);
             }
-
-            rows.push(row);
         }
 
-        self.tiles = rows;
+        self.tiles = tiles;
+        self.resync();
     }
 
     fn populate_with_air(&mut self) {
-        let mut rng = rand::thread_rng();
-
-        let mut rows: Vec<Vec<Tile>> = vec![];
-        for _ in 0..self.size_x {
-            let mut row: Vec<Tile> = vec![];
-            for _ in 0..self.size_y {
-                row.push(Tile::Air);
-            }
-            rows.push(row);
-        }
-
-        self.tiles = rows
+        self.tiles = vec![Tile::Air; self.size_x * self.size_y];
+        self.resync();
     }
 
     fn kill_sugar(&mut self) {
-        let mut rows: Vec<Vec<Tile>> = vec![];
-        for x in 0..self.size_x {
-            let mut row: Vec<Tile> = vec![];
-            for y in 0..self.size_y {
-                row.push(match self.get_tile(x, y) {
+        let mut tiles: Vec<Tile> = Vec::with_capacity(self.tiles.len());
+
+        for y in 0..self.size_y {
+            for x in 0..self.size_x {
+                tiles.push(match self.get_tile(x, y) {
                     Some(Tile::Sugar) => {
                         if self.has_water_in_neighbourhood(x, y) {
                             Tile::Sugar
@@ -129,10 +237,55 @@ impl Farm {
                     tile => tile.unwrap(),
                 })
             }
-            rows.push(row);
         }
 
-        self.tiles = rows;
+        self.tiles = tiles;
+        self.resync();
+    }
+
+    // Pads the farm with a one-tile ring of Air on every side, then re-runs
+    // `kill_sugar`. The new ring is Air, just as `get_neighbours` already
+    // treats off-the-edge neighbours, so no sugar tile loses its water; the
+    // call recounts sugar and symmetry matches (and the cached score) for
+    // the larger grid.
+    fn grow(&mut self) {
+        let new_size_x = self.size_x + 2;
+        let new_size_y = self.size_y + 2;
+        let mut tiles = vec![Tile::Air; new_size_x * new_size_y];
+
+        for y in 0..self.size_y {
+            for x in 0..self.size_x {
+                tiles[(y + 1) * new_size_x + (x + 1)] = self.tiles[self.idx(x, y)];
+            }
+        }
+
+        self.tiles = tiles;
+        self.size_x = new_size_x;
+        self.size_y = new_size_y;
+
+        self.kill_sugar();
+    }
+
+    // Whether any tile touching the outer edge is Sugar, i.e. the current
+    // size is cramped enough that growing the farm could still help.
+    fn border_has_sugar(&self) -> bool {
+        for x in 0..self.size_x {
+            if self.get_tile(x, 0) == Some(Tile::Sugar)
+                || self.get_tile(x, self.size_y - 1) == Some(Tile::Sugar)
+            {
+                return true;
+            }
+        }
+
+        for y in 0..self.size_y {
+            if self.get_tile(0, y) == Some(Tile::Sugar)
+                || self.get_tile(self.size_x - 1, y) == Some(Tile::Sugar)
+            {
+                return true;
+            }
+        }
+
+        false
     }
 
     fn has_water_in_neighbourhood(&self, x: usize, y: usize) -> bool {
@@ -163,92 +316,421 @@ impl Farm {
         vec![top, right, bottom, left]
     }
 
+    fn neighbour_positions(&self, x: usize, y: usize) -> Vec<(usize, usize)> {
+        let mut positions = vec![];
+        if y > 0 {
+            positions.push((x, y - 1));
+        }
+        if x + 1 < self.size_x {
+            positions.push((x + 1, y));
+        }
+        if y + 1 < self.size_y {
+            positions.push((x, y + 1));
+        }
+        if x > 0 {
+            positions.push((x - 1, y));
+        }
+
+        positions
+    }
+
+    fn idx(&self, x: usize, y: usize) -> usize {
+        y * self.size_x + x
+    }
+
     fn get_tile(&self, x: usize, y: usize) -> Option<Tile> {
         if x >= self.size_x || y >= self.size_y {
             return None;
         };
 
-        Some(self.tiles[y][x])
+        Some(self.tiles[self.idx(x, y)])
     }
 
-    fn get_sugar_score(&self) -> usize {
-        let mut score: usize = 0;
+    fn get_tile_overlay(
+        &self,
+        x: usize,
+        y: usize,
+        overlay: &[((usize, usize), Tile)],
+    ) -> Option<Tile> {
+        if let Some((_, tile)) = overlay.iter().find(|(pos, _)| *pos == (x, y)) {
+            return Some(*tile);
+        }
+
+        self.get_tile(x, y)
+    }
+
+    fn set_tile(&mut self, x: usize, y: usize, tile: Tile) {
+        let i = self.idx(x, y);
+        self.tiles[i] = tile;
+    }
 
-        for row in &self.tiles {
-            for tile in row {
-                match tile {
-                    Tile::Sugar => score += 100,
-                    _ => (),
+    fn has_other_water_neighbour(
+        &self,
+        x: usize,
+        y: usize,
+        exclude: (usize, usize),
+        overlay: &[((usize, usize), Tile)],
+    ) -> bool {
+        self.neighbour_positions(x, y)
+            .into_iter()
+            .filter(|pos| *pos != exclude)
+            .any(|(nx, ny)| self.get_tile_overlay(nx, ny, overlay) == Some(Tile::Water))
+    }
+
+    // The tile writes and raw sugar/symmetry count deltas that setting (x, y)
+    // to `new` would cause. `overlay` lets a second flip be evaluated as if
+    // an earlier flip (not yet committed) had already happened, so a swap
+    // move can be scored without mutating `self` or rescanning the grid.
+    fn tile_flip_delta(
+        &self,
+        x: usize,
+        y: usize,
+        new: Tile,
+        overlay: &[((usize, usize), Tile)],
+    ) -> MoveDelta {
+        let old_self = self.get_tile_overlay(x, y, overlay).unwrap();
+        let effective_new = if new == Tile::Sugar {
+            let has_water = self
+                .neighbour_positions(x, y)
+                .iter()
+                .any(|(nx, ny)| self.get_tile_overlay(*nx, *ny, overlay) == Some(Tile::Water));
+            if has_water {
+                Tile::Sugar
+            } else {
+                Tile::Air
+            }
+        } else {
+            new
+        };
+
+        let mut changes: Vec<((usize, usize), Tile)> = vec![];
+        if effective_new != old_self {
+            changes.push(((x, y), effective_new));
+        }
+
+        let was_water = old_self == Tile::Water;
+        let is_water = effective_new == Tile::Water;
+        if was_water && !is_water {
+            for (nx, ny) in self.neighbour_positions(x, y) {
+                if self.get_tile_overlay(nx, ny, overlay) == Some(Tile::Sugar)
+                    && !self.has_other_water_neighbour(nx, ny, (x, y), overlay)
+                {
+                    changes.push(((nx, ny), Tile::Air));
                 }
             }
         }
 
-        score
+        if changes.is_empty() {
+            return MoveDelta {
+                changes,
+                sugar_delta: 0,
+                vertical_delta: 0,
+                horizontal_delta: 0,
+            };
+        }
+
+        let sugar_delta: i64 = changes
+            .iter()
+            .map(|(pos, tile)| {
+                let before = self.get_tile_overlay(pos.0, pos.1, overlay).unwrap();
+                (*tile == Tile::Sugar) as i64 - (before == Tile::Sugar) as i64
+            })
+            .sum();
+
+        let vertical_delta =
+            self.matched_delta(&changes, overlay, |farm, px, py| (farm.size_x - 1 - px, py));
+        let horizontal_delta =
+            self.matched_delta(&changes, overlay, |farm, px, py| (px, farm.size_y - 1 - py));
+
+        MoveDelta {
+            changes,
+            sugar_delta,
+            vertical_delta,
+            horizontal_delta,
+        }
     }
 
-    fn get_vertical_symmetry_score(&self) -> usize {
-        let mut matched_tiles: usize = 0;
-
-        for row in &self.tiles {
-            let mut x = 0;
-            for tile in row {
-                let opposing_tile = row[self.size_x - 1 - x];
-                if *tile == opposing_tile {
-                    matched_tiles += 1;
-                };
-                x += 1;
+    // For a mirror relation (vertical or horizontal), only a changed cell and
+    // its mirror partner can flip from matching to mismatching or back, so
+    // the match-count delta only needs to touch that small set instead of
+    // rescanning every cell.
+    fn matched_delta<F>(
+        &self,
+        changes: &[((usize, usize), Tile)],
+        overlay: &[((usize, usize), Tile)],
+        mirror: F,
+    ) -> i64
+    where
+        F: Fn(&Farm, usize, usize) -> (usize, usize),
+    {
+        let value_before = |pos: (usize, usize)| self.get_tile_overlay(pos.0, pos.1, overlay).unwrap();
+        let value_after = |pos: (usize, usize)| {
+            changes
+                .iter()
+                .find(|(p, _)| *p == pos)
+                .map(|(_, tile)| *tile)
+                .unwrap_or_else(|| value_before(pos))
+        };
+
+        let mut touched: Vec<(usize, usize)> = vec![];
+        for (pos, _) in changes {
+            if !touched.contains(pos) {
+                touched.push(*pos);
+            }
+            let mirrored = mirror(self, pos.0, pos.1);
+            if !touched.contains(&mirrored) {
+                touched.push(mirrored);
             }
         }
 
+        touched
+            .into_iter()
+            .map(|pos| {
+                let mirrored = mirror(self, pos.0, pos.1);
+                let before = value_before(pos) == value_before(mirrored);
+                let after = value_after(pos) == value_after(mirrored);
+                after as i64 - before as i64
+            })
+            .sum()
+    }
+
+    fn get_sugar_score(&self) -> usize {
+        self.sugar_count * 100
+    }
+
+    fn get_vertical_symmetry_score(&self) -> usize {
         let total_tiles = self.size_x * self.size_y;
-        let symmetry_factor = matched_tiles as f64 / total_tiles as f64;
+        let symmetry_factor = self.vertical_matches as f64 / total_tiles as f64;
 
-        (symmetry_factor * 50.0) as usize
+        (symmetry_factor * self.weights.vertical) as usize
     }
 
     fn get_horizontal_symmetry_score(&self) -> usize {
-        let mut matched_tiles: usize = 0;
-
-        let mut y = 0;
-        for row in &self.tiles {
-            let mut x = 0;
-            for tile in row {
-                let opposing_tile = &self.tiles[self.size_y - 1 - y][x];
-                if *tile == *opposing_tile {
-                    matched_tiles += 1;
-                };
-                x += 1;
-            }
-            y += 1;
+        let total_tiles = self.size_x * self.size_y;
+        let symmetry_factor = self.horizontal_matches as f64 / total_tiles as f64;
+
+        (symmetry_factor * self.weights.horizontal) as usize
+    }
+
+    // Fraction of cells that land on an equal tile once `self` is put through
+    // `transform(rot, flip)`, i.e. how close `self` is to being invariant
+    // under that transform. Only meaningful for square farms, since a
+    // non-square farm's rotation changes its dimensions and can't line up
+    // cell-for-cell with the original.
+    fn transform_match_fraction(&self, rot: u8, flip: bool) -> f64 {
+        let transformed = self.transform(rot, flip);
+        if transformed.size_x != self.size_x || transformed.size_y != self.size_y {
+            return 0.0;
         }
 
         let total_tiles = self.size_x * self.size_y;
-        let symmetry_factor = matched_tiles as f64 / total_tiles as f64;
+        let matches = (0..self.size_y)
+            .flat_map(|y| (0..self.size_x).map(move |x| (x, y)))
+            .filter(|&(x, y)| self.get_tile(x, y) == transformed.get_tile(x, y))
+            .count();
 
-        (symmetry_factor * 50.0) as usize
+        matches as f64 / total_tiles as f64
     }
 
-    fn score(&self) -> usize {
-        let mut score: usize = 0;
+    fn get_rotational_symmetry_score(&self, rot: u8, weight: f64) -> usize {
+        (self.transform_match_fraction(rot, false) * weight) as usize
+    }
 
-        score += self.get_sugar_score();
+    fn get_diagonal_symmetry_score(&self) -> usize {
+        (self.transform_match_fraction(1, true) * self.weights.diagonal) as usize
+    }
 
-        score += self.get_vertical_symmetry_score();
+    // Sum of the terms the delta-scoring machinery (`score_delta`/`commit`)
+    // tracks incrementally. Kept separate from `score()` because the
+    // rotational/diagonal terms below are only ever evaluated via a full
+    // O(n) scan, so folding them into the running `cached_score` would mean
+    // recomputing them on every single-tile move.
+    fn core_score(&self) -> usize {
+        self.get_sugar_score() + self.get_vertical_symmetry_score() + self.get_horizontal_symmetry_score()
+    }
+
+    fn score(&self) -> usize {
+        let mut score = self.core_score();
 
-        score += self.get_horizontal_symmetry_score();
+        if self.size_x == self.size_y {
+            score += self.get_rotational_symmetry_score(1, self.weights.rotational_90);
+            score += self.get_rotational_symmetry_score(2, self.weights.rotational_180);
+            score += self.get_rotational_symmetry_score(3, self.weights.rotational_270);
+            score += self.get_diagonal_symmetry_score();
+        }
 
         score
     }
+
+    fn score_from_counts(
+        &self,
+        sugar_count: usize,
+        vertical_matches: usize,
+        horizontal_matches: usize,
+    ) -> usize {
+        let total_tiles = self.size_x * self.size_y;
+        let vertical_score =
+            ((vertical_matches as f64 / total_tiles as f64) * self.weights.vertical) as usize;
+        let horizontal_score =
+            ((horizontal_matches as f64 / total_tiles as f64) * self.weights.horizontal) as usize;
+
+        sugar_count * 100 + vertical_score + horizontal_score
+    }
+
+    // Cheap alternative to `core_score()` for evaluating a single proposed
+    // move: O(neighbourhood) instead of O(n), using the same cached counts
+    // `core_score()` itself now reads from.
+    fn score_delta(&self, x: usize, y: usize, new: Tile) -> i64 {
+        let computed = self.tile_flip_delta(x, y, new, &[]);
+        let sugar = (self.sugar_count as i64 + computed.sugar_delta) as usize;
+        let vertical = (self.vertical_matches as i64 + computed.vertical_delta) as usize;
+        let horizontal = (self.horizontal_matches as i64 + computed.horizontal_delta) as usize;
+
+        self.score_from_counts(sugar, vertical, horizontal) as i64 - self.core_score() as i64
+    }
+
+    // Applies the single-tile move `score_delta` describes, updating the
+    // cached counts and running score in place. Returns the score delta.
+    fn apply_move(&mut self, x: usize, y: usize, new: Tile) -> i64 {
+        let computed = self.tile_flip_delta(x, y, new, &[]);
+        self.commit(computed)
+    }
+
+    fn commit(&mut self, computed: MoveDelta) -> i64 {
+        let old_score = self.core_score() as i64;
+
+        for (pos, tile) in &computed.changes {
+            self.set_tile(pos.0, pos.1, *tile);
+        }
+        self.sugar_count = (self.sugar_count as i64 + computed.sugar_delta) as usize;
+        self.vertical_matches = (self.vertical_matches as i64 + computed.vertical_delta) as usize;
+        self.horizontal_matches =
+            (self.horizontal_matches as i64 + computed.horizontal_delta) as usize;
+
+        #[cfg(debug_assertions)]
+        {
+            // `core_score()` derives its result from the same running counts
+            // this move just updated, so comparing it against `cached_score`
+            // would be tautological. Rescan the grid from scratch instead.
+            let mut scanned = self.clone();
+            scanned.sync_counts();
+            debug_assert_eq!(self.sugar_count, scanned.sugar_count);
+            debug_assert_eq!(self.vertical_matches, scanned.vertical_matches);
+            debug_assert_eq!(self.horizontal_matches, scanned.horizontal_matches);
+        }
+
+        let new_score = self.core_score() as i64;
+        self.cached_score += new_score - old_score;
+
+        new_score - old_score
+    }
+
+    fn sync_counts(&mut self) {
+        let mut sugar_count = 0;
+        let mut vertical_matches = 0;
+        let mut horizontal_matches = 0;
+
+        for y in 0..self.size_y {
+            for x in 0..self.size_x {
+                let tile = self.get_tile(x, y).unwrap();
+                if tile == Tile::Sugar {
+                    sugar_count += 1;
+                }
+                if tile == self.get_tile(self.size_x - 1 - x, y).unwrap() {
+                    vertical_matches += 1;
+                }
+                if tile == self.get_tile(x, self.size_y - 1 - y).unwrap() {
+                    horizontal_matches += 1;
+                }
+            }
+        }
+
+        self.sugar_count = sugar_count;
+        self.vertical_matches = vertical_matches;
+        self.horizontal_matches = horizontal_matches;
+    }
+
+    fn resync(&mut self) {
+        self.sync_counts();
+        self.cached_score = self.core_score() as i64;
+    }
+
+    // Builds a new farm holding `self` rotated 90 degrees clockwise `rot`
+    // times and then, if `flip`, mirrored left-right. Composing a rotation
+    // with a flip this way reaches every symmetry of a square (including
+    // the two diagonal mirrors), so this single method backs all of the
+    // rotational/diagonal symmetry scores above.
+    fn transform(&self, rot: u8, flip: bool) -> Farm {
+        let mut tiles = self.tiles.clone();
+        let mut size_x = self.size_x;
+        let mut size_y = self.size_y;
+
+        for _ in 0..(rot % 4) {
+            let (rotated, new_size_x, new_size_y) = rotate90(&tiles, size_x, size_y);
+            tiles = rotated;
+            size_x = new_size_x;
+            size_y = new_size_y;
+        }
+
+        if flip {
+            tiles = flip_horizontal(&tiles, size_x, size_y);
+        }
+
+        let mut farm = Farm {
+            tiles,
+            size_x,
+            size_y,
+            sugar_count: 0,
+            vertical_matches: 0,
+            horizontal_matches: 0,
+            cached_score: 0,
+            weights: self.weights,
+        };
+        farm.resync();
+        farm
+    }
+}
+
+// Rotates a size_x (cols) by size_y (rows) tile grid 90 degrees clockwise.
+// For a row r and column c, the tile lands at row c, column (size_y - 1 - r)
+// of the new, transposed grid.
+fn rotate90(tiles: &[Tile], size_x: usize, size_y: usize) -> (Vec<Tile>, usize, usize) {
+    let new_size_x = size_y;
+    let new_size_y = size_x;
+    let mut rotated = vec![Tile::Air; new_size_x * new_size_y];
+
+    for r in 0..size_y {
+        for c in 0..size_x {
+            let new_r = c;
+            let new_c = size_y - 1 - r;
+            rotated[new_r * new_size_x + new_c] = tiles[r * size_x + c];
+        }
+    }
+
+    (rotated, new_size_x, new_size_y)
+}
+
+// Mirrors a tile grid left-right, keeping its dimensions unchanged.
+fn flip_horizontal(tiles: &[Tile], size_x: usize, size_y: usize) -> Vec<Tile> {
+    let mut flipped = vec![Tile::Air; size_x * size_y];
+
+    for r in 0..size_y {
+        for c in 0..size_x {
+            flipped[r * size_x + (size_x - 1 - c)] = tiles[r * size_x + c];
+        }
+    }
+
+    flipped
 }
 
 impl Display for Farm {
     fn fmt(&self, formatter: &mut Formatter) -> Result {
-        for row in &self.tiles {
-            for tile in row {
+        for y in 0..self.size_y {
+            for x in 0..self.size_x {
                 write!(
                     formatter,
                     "{}",
-                    match tile {
+                    match self.get_tile(x, y).unwrap() {
                         Tile::Water => "~",
                         Tile::Sugar => "x",
                         Tile::Air => " ",
@@ -261,34 +743,278 @@ impl Display for Farm {
     }
 }
 
-fn main() {
-    let size = 4;
+fn random_tile<R: Rng>(rng: &mut R) -> Tile {
+    match rng.gen_range(0..3) {
+        0 => Tile::Sugar,
+        1 => Tile::Water,
+        2 => Tile::Air,
+        _ => panic!("invalid tile index"),
+    }
+}
+
+// Time-budgeted local search: starts from a fully randomized farm and
+// repeatedly proposes a single-tile or swap move, accepting worsening
+// moves with probability exp(-d/T) on a decaying temperature schedule.
+// Returns the best farm seen, separate from the (possibly worse) current
+// state left in `farm`.
+//
+// NOTE: the loop is bounded by `start.elapsed() < time_limit`, so the
+// number of RNG draws (and therefore the winner) varies run to run even
+// with the same `--seed`. `--seed` reproducibility is a GA-only guarantee;
+// see the note in `main`.
+fn anneal<R: Rng>(
+    farm: &mut Farm,
+    time_limit: Duration,
+    max_size: usize,
+    stagnation_threshold: usize,
+    rng: &mut R,
+) -> Farm {
+    farm.mutate(1.0, rng);
+    farm.kill_sugar();
+
+    let mut best = farm.clone();
+    let mut best_score = farm.score();
+    let mut current_score = best_score;
+    let mut stagnant_moves = 0;
+
+    let t0 = 10.0_f64;
+    let t1 = 0.01_f64;
+    let start = Instant::now();
+
+    while start.elapsed() < time_limit {
+        let progress = start.elapsed().as_secs_f64() / time_limit.as_secs_f64();
+        let temperature = t0 * (t1 / t0).powf(progress);
+
+        // Square farms also score rotational/diagonal symmetry, which only
+        // exists as a full O(n) `score()` scan (see `core_score`'s doc
+        // comment) and isn't part of the O(neighbourhood) sugar/mirror delta
+        // below. Evaluating moves on the delta alone would let the annealer
+        // "improve" while silently tanking the rotational terms it prints at
+        // the end, so square farms fall back to a full rescore per move.
+        let is_square = farm.size_x == farm.size_y;
+
+        let x = rng.gen_range(0..farm.size_x);
+        let y = rng.gen_range(0..farm.size_y);
+        let old_tile = farm.get_tile(x, y).unwrap();
+        let swap_target = if rng.gen_bool(0.5) {
+            Some((rng.gen_range(0..farm.size_x), rng.gen_range(0..farm.size_y)))
+        } else {
+            None
+        };
+
+        let accepted_score = match swap_target.filter(|pos| *pos != (x, y)) {
+            Some((sx, sy)) => {
+                let swap_tile = farm.get_tile(sx, sy).unwrap();
+
+                if is_square {
+                    let mut candidate = farm.clone();
+                    let d1 = candidate.tile_flip_delta(x, y, swap_tile, &[]);
+                    candidate.commit(d1);
+                    let d2 = candidate.tile_flip_delta(sx, sy, old_tile, &[]);
+                    candidate.commit(d2);
+                    let new_score = candidate.score();
+                    let delta = new_score as i64 - current_score as i64;
+
+                    if delta >= 0 || rng.gen::<f64>() < (delta as f64 / temperature).exp() {
+                        *farm = candidate;
+                        Some(new_score)
+                    } else {
+                        None
+                    }
+                } else {
+                    let d1 = farm.tile_flip_delta(x, y, swap_tile, &[]);
+                    let d2 = farm.tile_flip_delta(sx, sy, old_tile, &d1.changes);
+
+                    let sugar =
+                        (farm.sugar_count as i64 + d1.sugar_delta + d2.sugar_delta) as usize;
+                    let vertical = (farm.vertical_matches as i64
+                        + d1.vertical_delta
+                        + d2.vertical_delta) as usize;
+                    let horizontal = (farm.horizontal_matches as i64
+                        + d1.horizontal_delta
+                        + d2.horizontal_delta) as usize;
+                    let new_score = farm.score_from_counts(sugar, vertical, horizontal);
+                    let delta = new_score as i64 - current_score as i64;
+
+                    if delta >= 0 || rng.gen::<f64>() < (delta as f64 / temperature).exp() {
+                        farm.commit(d1);
+                        farm.commit(d2);
+                        Some(new_score)
+                    } else {
+                        None
+                    }
+                }
+            }
+            None => {
+                let new_tile = random_tile(rng);
+
+                if is_square {
+                    let mut candidate = farm.clone();
+                    candidate.apply_move(x, y, new_tile);
+                    let new_score = candidate.score();
+                    let delta = new_score as i64 - current_score as i64;
+
+                    if delta >= 0 || rng.gen::<f64>() < (delta as f64 / temperature).exp() {
+                        *farm = candidate;
+                        Some(new_score)
+                    } else {
+                        None
+                    }
+                } else {
+                    let delta = farm.score_delta(x, y, new_tile);
+                    let new_score = (current_score as i64 + delta) as usize;
+
+                    if delta >= 0 || rng.gen::<f64>() < (delta as f64 / temperature).exp() {
+                        farm.apply_move(x, y, new_tile);
+                        Some(new_score)
+                    } else {
+                        None
+                    }
+                }
+            }
+        };
+
+        if let Some(new_score) = accepted_score {
+            current_score = new_score;
+            if new_score > best_score {
+                best_score = new_score;
+                best = farm.clone();
+                stagnant_moves = 0;
+            } else {
+                stagnant_moves += 1;
+            }
+        } else {
+            stagnant_moves += 1;
+        }
+
+        if stagnant_moves >= stagnation_threshold && farm.size_x < max_size && farm.border_has_sugar()
+        {
+            farm.grow();
+            current_score = farm.score();
+            stagnant_moves = 0;
+        }
+    }
+
+    best
+}
+
+fn parse_optimizer() -> Optimizer {
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--sa" | "--anneal" => return Optimizer::Annealing,
+            "--ga" | "--genetic" => return Optimizer::Genetic,
+            _ => {}
+        }
+    }
+
+    Optimizer::Genetic
+}
+
+// Returns the value following `flag` in argv, e.g. `parse_flag_value("--seed")`
+// for `... --seed 42 ...`.
+fn parse_flag_value(flag: &str) -> Option<String> {
+    let args: Vec<String> = std::env::args().collect();
+    for (i, arg) in args.iter().enumerate() {
+        if arg == flag {
+            return args.get(i + 1).cloned();
+        }
+    }
+
+    None
+}
+
+fn parse_seed() -> Option<u64> {
+    parse_flag_value("--seed")?.parse().ok()
+}
+
+fn parse_max_size(default: usize) -> usize {
+    parse_flag_value("--max-size")
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default)
+}
+
+fn parse_stagnation(default: usize) -> usize {
+    parse_flag_value("--stagnation")
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default)
+}
+
+// Per-term symmetry weights, each overridable with its own `--weight-*` flag;
+// any flag left unset keeps `SymmetryWeights::default()`'s 50.0 for that term.
+fn parse_weights() -> SymmetryWeights {
+    let defaults = SymmetryWeights::default();
+    let weight = |flag: &str, default: f64| -> f64 {
+        parse_flag_value(flag)
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(default)
+    };
+
+    SymmetryWeights {
+        vertical: weight("--weight-vertical", defaults.vertical),
+        horizontal: weight("--weight-horizontal", defaults.horizontal),
+        rotational_90: weight("--weight-rotational-90", defaults.rotational_90),
+        rotational_180: weight("--weight-rotational-180", defaults.rotational_180),
+        rotational_270: weight("--weight-rotational-270", defaults.rotational_270),
+        diagonal: weight("--weight-diagonal", defaults.diagonal),
+    }
+}
+
+fn run_genetic<R: Rng>(
+    size: usize,
+    max_size: usize,
+    stagnation_threshold: usize,
+    weights: SymmetryWeights,
+    rng: &mut R,
+) {
     let popluation = 500;
     let generations = size * 1000;
     let mutation_factor = 0.1;
 
     let mut farms: Vec<Farm> = vec![];
     for _ in 0..popluation {
-        farms.push(Farm::new_square(size));
+        farms.push(Farm::new_square(size, weights));
     }
 
     print_scores(&farms);
 
+    let mut best_score = 0;
+    let mut stagnant_generations = 0;
+
     for generation in 0..generations {
         farms.sort_by(|a, b| a.score().partial_cmp(&b.score()).unwrap());
         while farms.len() > popluation / 3 {
             farms.remove(0);
         }
         while farms.len() < popluation {
-            let mut rng = rand::thread_rng();
             let mut new_farm = Farm::breed(
                 &farms[rng.gen_range(0..(popluation / 3))],
                 &farms[rng.gen_range(0..(popluation / 3))],
             );
-            new_farm.mutate(mutation_factor);
+            new_farm.mutate(mutation_factor, rng);
             new_farm.kill_sugar();
             farms.push(new_farm);
         }
+
+        let (current_best, best_size_x, best_border_sugar) = {
+            let best_farm = farms.iter().max_by_key(|farm| farm.score()).unwrap();
+            (best_farm.score(), best_farm.size_x, best_farm.border_has_sugar())
+        };
+
+        if current_best > best_score {
+            best_score = current_best;
+            stagnant_generations = 0;
+        } else {
+            stagnant_generations += 1;
+        }
+
+        if stagnant_generations >= stagnation_threshold && best_size_x < max_size && best_border_sugar
+        {
+            for farm in farms.iter_mut() {
+                farm.grow();
+            }
+            stagnant_generations = 0;
+        }
+
         if generation % (size * 10) == 0 {
             print!("gen #{}: ", generation + 1);
             print_scores(&farms);
@@ -302,6 +1028,71 @@ fn main() {
     println!("{} from sugar", winner.get_sugar_score());
     println!("{} from x symm", winner.get_vertical_symmetry_score());
     println!("{} from y symm", winner.get_horizontal_symmetry_score());
+    if winner.size_x == winner.size_y {
+        println!(
+            "{} from rotational symm",
+            winner.get_rotational_symmetry_score(1, winner.weights.rotational_90)
+                + winner.get_rotational_symmetry_score(2, winner.weights.rotational_180)
+                + winner.get_rotational_symmetry_score(3, winner.weights.rotational_270)
+        );
+        println!("{} from diagonal symm", winner.get_diagonal_symmetry_score());
+    }
+}
+
+fn run_annealing<R: Rng>(
+    size: usize,
+    max_size: usize,
+    stagnation_threshold: usize,
+    weights: SymmetryWeights,
+    rng: &mut R,
+) {
+    let mut farm = Farm::new_square(size, weights);
+    let winner = anneal(
+        &mut farm,
+        Duration::from_millis(950),
+        max_size,
+        stagnation_threshold,
+        rng,
+    );
+
+    println!("{}", winner);
+    println!("{} from sugar", winner.get_sugar_score());
+    println!("{} from x symm", winner.get_vertical_symmetry_score());
+    println!("{} from y symm", winner.get_horizontal_symmetry_score());
+    if winner.size_x == winner.size_y {
+        println!(
+            "{} from rotational symm",
+            winner.get_rotational_symmetry_score(1, winner.weights.rotational_90)
+                + winner.get_rotational_symmetry_score(2, winner.weights.rotational_180)
+                + winner.get_rotational_symmetry_score(3, winner.weights.rotational_270)
+        );
+        println!("{} from diagonal symm", winner.get_diagonal_symmetry_score());
+    }
+}
+
+fn main() {
+    let size = 4;
+    let max_size = parse_max_size(8);
+    let stagnation_threshold = parse_stagnation(50);
+    let weights = parse_weights();
+
+    let seed = parse_seed().unwrap_or_else(|| rand::thread_rng().gen());
+    println!("seed: {}", seed);
+    let mut rng = Xoshiro256StarStar::seed_from_u64(seed);
+
+    let optimizer = parse_optimizer();
+    if optimizer == Optimizer::Annealing {
+        // Unlike the GA, `anneal`'s loop is bounded by wall-clock time, so
+        // the same seed can still take a different number of steps (and
+        // land on a different winner) between runs. The seed only makes the
+        // RNG stream deterministic, not the run itself.
+        println!("note: --seed makes the SA run's RNG stream deterministic, not its wall-clock-bounded step count or winner");
+    }
+
+    match optimizer {
+        Optimizer::Genetic => run_genetic(size, max_size, stagnation_threshold, weights, &mut rng),
+        Optimizer::Annealing => run_annealing(size, max_size, stagnation_threshold, weights, &mut rng),
+    }
 }
 
 fn print_scores(farms: &Vec<Farm>) {
@@ -326,3 +1117,159 @@ fn print_scores(farms: &Vec<Farm>) {
         max, min, avg, sugar_avg, symm_avg
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn farm_from_rows(rows: &[&str]) -> Farm {
+        let size_y = rows.len();
+        let size_x = rows[0].len();
+        let tiles = rows
+            .iter()
+            .flat_map(|row| row.chars())
+            .map(|c| match c {
+                'x' => Tile::Sugar,
+                '~' => Tile::Water,
+                '.' => Tile::Air,
+                other => unreachable!("unexpected tile char: {other}"),
+            })
+            .collect();
+
+        let mut farm = Farm {
+            tiles,
+            size_x,
+            size_y,
+            sugar_count: 0,
+            vertical_matches: 0,
+            horizontal_matches: 0,
+            cached_score: 0,
+            weights: SymmetryWeights::default(),
+        };
+        farm.resync();
+        farm
+    }
+
+    // Full, from-scratch recompute of the score a single-tile move would
+    // produce, independent of `score_delta`/`commit`'s incremental bookkeeping.
+    fn recompute_core_score_after(farm: &Farm, x: usize, y: usize, new: Tile) -> i64 {
+        let mut scanned = farm.clone();
+        scanned.set_tile(x, y, new);
+        scanned.kill_sugar();
+        scanned.core_score() as i64
+    }
+
+    #[test]
+    fn score_delta_matches_full_recompute_for_sugar_placement() {
+        let farm = farm_from_rows(&["~..", "...", "..."]);
+
+        for &(x, y) in &[(1, 0), (0, 1), (2, 2)] {
+            let delta = farm.score_delta(x, y, Tile::Sugar);
+            let expected =
+                recompute_core_score_after(&farm, x, y, Tile::Sugar) - farm.core_score() as i64;
+            assert_eq!(delta, expected, "mismatch placing sugar at ({x}, {y})");
+        }
+    }
+
+    #[test]
+    fn score_delta_matches_full_recompute_for_water_removal_cascade() {
+        // A sugar tile whose only water neighbour is removed must also turn
+        // to Air -- this exercises `tile_flip_delta`'s multi-tile cascade.
+        let farm = farm_from_rows(&["x~x", "...", "..."]);
+        assert_eq!(farm.get_tile(0, 0), Some(Tile::Sugar));
+        assert_eq!(farm.get_tile(2, 0), Some(Tile::Sugar));
+
+        let delta = farm.score_delta(1, 0, Tile::Air);
+        let expected =
+            recompute_core_score_after(&farm, 1, 0, Tile::Air) - farm.core_score() as i64;
+        assert_eq!(delta, expected);
+    }
+
+    #[test]
+    fn score_delta_matches_full_recompute_for_swap_moves() {
+        let farm = farm_from_rows(&["x~.", ".x.", "~.."]);
+
+        let (x, y) = (0, 0);
+        let (sx, sy) = (2, 1);
+        let old_tile = farm.get_tile(x, y).unwrap();
+        let swap_tile = farm.get_tile(sx, sy).unwrap();
+
+        let d1 = farm.tile_flip_delta(x, y, swap_tile, &[]);
+        let d2 = farm.tile_flip_delta(sx, sy, old_tile, &d1.changes);
+        let sugar = (farm.sugar_count as i64 + d1.sugar_delta + d2.sugar_delta) as usize;
+        let vertical =
+            (farm.vertical_matches as i64 + d1.vertical_delta + d2.vertical_delta) as usize;
+        let horizontal =
+            (farm.horizontal_matches as i64 + d1.horizontal_delta + d2.horizontal_delta) as usize;
+        let incremental = farm.score_from_counts(sugar, vertical, horizontal) as i64;
+
+        let mut scanned = farm.clone();
+        scanned.set_tile(x, y, swap_tile);
+        scanned.set_tile(sx, sy, old_tile);
+        scanned.kill_sugar();
+
+        assert_eq!(incremental, scanned.core_score() as i64);
+    }
+
+    #[test]
+    fn transform_round_trips_through_four_rotations() {
+        let farm = farm_from_rows(&["x~.", ".x~", "~.x"]);
+
+        let rotated_four_times = farm
+            .transform(1, false)
+            .transform(1, false)
+            .transform(1, false)
+            .transform(1, false);
+
+        assert_eq!(rotated_four_times.tiles, farm.tiles);
+        assert_eq!(
+            (rotated_four_times.size_x, rotated_four_times.size_y),
+            (farm.size_x, farm.size_y)
+        );
+    }
+
+    #[test]
+    fn transform_flip_is_its_own_inverse() {
+        let farm = farm_from_rows(&["x~.", ".x~", "~.x"]);
+
+        let flipped_twice = farm.transform(0, true).transform(0, true);
+        assert_eq!(flipped_twice.tiles, farm.tiles);
+    }
+
+    #[test]
+    fn rotate90_matches_hand_worked_fixture() {
+        // 2 rows x 3 cols -> 3 rows x 2 cols after a clockwise rotation.
+        #[rustfmt::skip]
+        let tiles = vec![
+            Tile::Sugar, Tile::Water, Tile::Air,
+            Tile::Air, Tile::Sugar, Tile::Water,
+        ];
+        let (rotated, new_size_x, new_size_y) = rotate90(&tiles, 3, 2);
+
+        assert_eq!((new_size_x, new_size_y), (2, 3));
+        #[rustfmt::skip]
+        let expected = vec![
+            Tile::Air, Tile::Sugar,
+            Tile::Sugar, Tile::Water,
+            Tile::Water, Tile::Air,
+        ];
+        assert_eq!(rotated, expected);
+    }
+
+    #[test]
+    fn flip_horizontal_mirrors_each_row() {
+        #[rustfmt::skip]
+        let tiles = vec![
+            Tile::Sugar, Tile::Water, Tile::Air,
+            Tile::Air, Tile::Sugar, Tile::Water,
+        ];
+        let flipped = flip_horizontal(&tiles, 3, 2);
+
+        #[rustfmt::skip]
+        let expected = vec![
+            Tile::Air, Tile::Water, Tile::Sugar,
+            Tile::Water, Tile::Sugar, Tile::Air,
+        ];
+        assert_eq!(flipped, expected);
+    }
+}